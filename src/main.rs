@@ -2,132 +2,703 @@ use std::time::Instant;
 
 
 mod h4sh {
+    use std::cmp::Eq;
+    use std::collections::hash_map::RandomState;
     use std::fmt::Debug;
-    use std::cmp::PartialEq;
+    use std::hash::{BuildHasher, Hash};
 
+    // Number of slots scanned together as one SIMD-sized "group", SwissTable-style.
+    const GROUP_SIZE: usize = 16;
 
-    trait Hashable {
-        fn hash(&self) -> usize;
+    // Sentinel control byte for an empty slot. It has the high bit set, so a
+    // single `& 0x80` test tells "available for insert" apart from an
+    // occupied tag byte, which is always `hash & 0x7f` and therefore always
+    // has the high bit clear. There's no tombstone sentinel: `remove` closes
+    // the gap it leaves via backward-shift instead, so EMPTY is the only
+    // control byte that isn't a tag.
+    const EMPTY: u8 = 0xFF;
+
+    fn broadcast(byte: u8) -> u128 {
+        u128::from_ne_bytes([byte; GROUP_SIZE])
     }
 
-    impl Hashable for String {
-        // http://www.cse.yorku.ca/~oz/hash.html
-        fn hash(&self) -> usize {
-            let mut res: usize = 5381;
-            for c in self.bytes() {
-                res = ((res << 5).wrapping_add(res)).wrapping_add(c.into());
-            }
-            res
-        }
+    // Classic SWAR "has zero byte" trick: a zero byte borrows into its high
+    // bit when 1 is subtracted from it while its original high bit was 0.
+    // The lowest zero byte is always flagged exactly, but the borrow can also
+    // flag a 0x01 byte sitting just above a zero byte, so the result is
+    // nonzero iff some byte is zero, and individual higher lanes can be false
+    // positives.
+    fn has_zero_byte(x: u128) -> u128 {
+        const LO: u128 = 0x01010101_01010101_01010101_01010101;
+        const HI: u128 = 0x80808080_80808080_80808080_80808080;
+        x.wrapping_sub(LO) & !x & HI
     }
 
-    impl Hashable for usize {
-        fn hash(&self) -> usize {
-            *self
-        }
+    // Bitmask (one set bit per matching lane, at that lane's high bit) of the
+    // control bytes in `group` that equal `byte`. Every equal lane is set; a
+    // lane above an equal one may also be set without matching, so callers
+    // compare the full key before trusting a lane.
+    fn match_byte_mask(group: u128, byte: u8) -> u128 {
+        has_zero_byte(group ^ broadcast(byte))
     }
 
-#[derive(Default, Clone)]
-    struct HashCell<Key, Value> {
-        key: Key,
-        value: Value,
-        taken: bool,
+    fn matching_slots(mask: u128) -> impl Iterator<Item = usize> {
+        (0..GROUP_SIZE).filter(move |slot| (mask >> (slot * 8 + 7)) & 1 == 1)
     }
 
-    pub struct HashTable<Key, Value> {
-        cells: Vec<HashCell<Key, Value>>,
+    pub struct HashTable<Key, Value, S = RandomState> {
+        control: Vec<u8>,
+        keys: Vec<Key>,
+        values: Vec<Value>,
+        // Robin Hood bookkeeping: how many slots past the first slot of its
+        // own ideal group each taken slot had to travel before finding a home. Meaningless
+        // for EMPTY slots.
+        probe_distance: Vec<usize>,
         taken_count: usize,
+        max_load_factor: f64,
+        hash_builder: S,
     }
 
-    impl<Key, Value> HashTable<Key, Value>
+    impl<Key, Value> HashTable<Key, Value, RandomState>
     where
-        Key: Default + Clone + Debug + PartialEq + Hashable,
+        Key: Default + Clone + Debug + Eq + Hash,
         Value: Default + Clone + Debug
     {
+        // matches the 90.9% (10/11) load factor std's DefaultResizePolicy targets
+        const DEFAULT_MAX_LOAD_FACTOR: f64 = 10.0 / 11.0;
 
         pub fn new() -> Self {
-            const INITIAL_CAP: usize = 11;
+            Self::with_load_factor(Self::DEFAULT_MAX_LOAD_FACTOR)
+        }
+
+        pub fn with_load_factor(max_load_factor: f64) -> Self {
+            Self::with_hasher(max_load_factor, RandomState::new())
+        }
+    }
+
+    impl<Key, Value, S> HashTable<Key, Value, S>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+        S: BuildHasher + Clone,
+    {
+        // RandomState wraps SipHash-1-3 with a per-table random key, so
+        // adversarial input can't force every key into the same probe chain.
+        pub fn with_hasher(max_load_factor: f64, hash_builder: S) -> Self {
+            assert!(max_load_factor > 0.0 && max_load_factor <= 1.0);
+            const INITIAL_GROUPS: usize = 1;
             Self {
-                cells: vec![HashCell::<_, _>::default(); INITIAL_CAP],
+                control: vec![EMPTY; INITIAL_GROUPS * GROUP_SIZE],
+                keys: vec![Key::default(); INITIAL_GROUPS * GROUP_SIZE],
+                values: vec![Value::default(); INITIAL_GROUPS * GROUP_SIZE],
+                probe_distance: vec![0; INITIAL_GROUPS * GROUP_SIZE],
                 taken_count: 0,
+                max_load_factor,
+                hash_builder,
             }
         }
 
+        fn capacity(&self) -> usize {
+            self.control.len()
+        }
+
+        fn num_groups(&self) -> usize {
+            self.capacity() / GROUP_SIZE
+        }
+
+        fn load_group(&self, base: usize) -> u128 {
+            let mut bytes = [0u8; GROUP_SIZE];
+            bytes.copy_from_slice(&self.control[base..base + GROUP_SIZE]);
+            u128::from_ne_bytes(bytes)
+        }
+
+        fn hash(&self, key: &Key) -> usize {
+            self.hash_builder.hash_one(key) as usize
+        }
+
+        // Ideal starting slot for `hash`: the first index of the first group
+        // its probe sequence visits.
+        fn ideal_base(&self, hash: usize) -> usize {
+            ((hash >> 7) % self.num_groups()) * GROUP_SIZE
+        }
+
+        // How far `index` sits past `base`, wrapping around the table.
+        fn distance_from(&self, base: usize, index: usize) -> usize {
+            (index + self.capacity() - base) % self.capacity()
+        }
+
+        fn is_overloaded(&self) -> bool {
+            self.taken_count as f64 > self.max_load_factor * self.capacity() as f64
+        }
+
         fn debug_dump(&self) {
-            for cell in self.cells.iter() {
-                if cell.taken {
-                    println!("{:?} -> {:?}", cell.key, cell.value);
+            for index in 0..self.capacity() {
+                if self.control[index] & 0x80 == 0 {
+                    println!("{:?} -> {:?}", self.keys[index], self.values[index]);
                 } else {
                     println!("x");
                 }
             }
         }
 
-        pub fn extend(&mut self) {
-            assert!(self.cells.len() > 0);
+        // Doubles the backing storage. Named `grow` (not `extend`) so it
+        // doesn't collide with the `Extend` trait impl below.
+        fn grow(&mut self) {
+            assert!(self.capacity() > 0);
+            let new_capacity = self.capacity() * 2;
             let mut new_self = Self {
-                cells: vec![HashCell::<_, _>::default(); self.cells.len() * 2 + 1],
+                control: vec![EMPTY; new_capacity],
+                keys: vec![Key::default(); new_capacity],
+                values: vec![Value::default(); new_capacity],
+                probe_distance: vec![0; new_capacity],
                 taken_count: 0,
+                max_load_factor: self.max_load_factor,
+                hash_builder: self.hash_builder.clone(),
             };
 
-            for cell in self.cells.iter() {
-                if cell.taken {
-                    new_self.insert(cell.key.clone(), cell.value.clone());
+            for index in 0..self.capacity() {
+                if self.control[index] & 0x80 == 0 {
+                    new_self.insert(self.keys[index].clone(), self.values[index].clone());
                 }
             }
 
             *self = new_self;
         }
 
+        // Robin Hood insertion: walk the probe sequence slot by slot; whichever
+        // of the incoming item and the current occupant has travelled farther
+        // from its own ideal slot keeps the slot, and the loser keeps probing
+        // from there. This equalizes probe lengths instead of letting early
+        // keys accumulate long runs while late keys get pushed arbitrarily far.
+        // Groups are tried in strictly increasing order rather than the
+        // quadratic group skipping a bare SwissTable lookup would use: that's
+        // what lets "the slot right after this one in memory" always mean
+        // "the next slot this key's probe would visit" too, which is exactly
+        // what `remove`'s backward-shift needs to close a hole without a
+        // tombstone (chunk0-3's explicit, already-shipped requirement).
+        //
+        // Returns the slot the originally-passed-in key ends up in, even
+        // though displacement may move it there partway through the probe,
+        // so callers like `VacantEntry::insert` can index straight into it
+        // without a second hash/probe.
+        fn insert_with_robin_hood(&mut self, hash: usize, mut key: Key, mut value: Value) -> usize {
+            let base = self.ideal_base(hash);
+            let mut control = (hash & 0x7f) as u8;
+            let mut index = base;
+            let mut dist = 0;
+            let mut carrying_original = true;
+            let mut original_index = None;
+
+            loop {
+                if self.control[index] & 0x80 != 0 {
+                    self.control[index] = control;
+                    self.keys[index] = key;
+                    self.values[index] = value;
+                    self.probe_distance[index] = dist;
+                    if carrying_original {
+                        original_index = Some(index);
+                    }
+                    return original_index.expect("the original key is always placed somewhere");
+                }
+
+                if self.probe_distance[index] < dist {
+                    std::mem::swap(&mut control, &mut self.control[index]);
+                    std::mem::swap(&mut key, &mut self.keys[index]);
+                    std::mem::swap(&mut value, &mut self.values[index]);
+                    std::mem::swap(&mut dist, &mut self.probe_distance[index]);
+
+                    if carrying_original {
+                        original_index = Some(index);
+                        carrying_original = false;
+                    }
+                }
+
+                index = (index + 1) % self.capacity();
+                dist += 1;
+            }
+        }
+
         pub fn insert(&mut self, key: Key, new_value: Value) {
             if let Some(old_value) = self.get_mut(&key) {
                 *old_value = new_value;
             } else {
-                if self.taken_count >= self.cells.len() {
-                    self.extend();
+                if self.is_overloaded() {
+                    self.grow();
                 }
-                assert!(self.taken_count < self.cells.len());
+                assert!(self.taken_count < self.capacity());
 
-                let mut index = key.hash() % self.cells.len();
+                let hash = self.hash(&key);
+                self.insert_with_robin_hood(hash, key, new_value);
+                self.taken_count += 1;
+            }
+        }
 
-                while self.cells[index].taken {
-                    index = (index + 1) % self.cells.len();
+        // Looks up `key` given its already-computed `hash`, so callers that
+        // need the hash for another reason (e.g. `entry`) don't hash twice.
+        //
+        // Groups are still scanned 16 lanes at a time via the control-byte
+        // bitmask tricks, but in strictly increasing order (no quadratic
+        // group skipping), so slot distance is monotonic along the scan and
+        // the Robin Hood invariant below holds: once a taken slot's own
+        // probe distance is shorter than how far this key has already
+        // travelled, the key cannot be any further down the chain.
+        fn locate(&self, hash: usize, key: &Key) -> Option<usize> {
+            let h2 = (hash & 0x7f) as u8;
+            let num_groups = self.num_groups();
+            let base = self.ideal_base(hash);
+            let mut group = base / GROUP_SIZE;
+
+            for _ in 0..num_groups {
+                let group_base = group * GROUP_SIZE;
+                let word = self.load_group(group_base);
+
+                for slot in matching_slots(match_byte_mask(word, h2)) {
+                    let index = group_base + slot;
+                    if self.keys[index] == *key {
+                        return Some(index);
+                    }
                 }
 
-                self.cells[index].taken = true;
-                self.cells[index].key = key;
-                self.cells[index].value = new_value;
-                self.taken_count += 1;
+                // An empty lane means the probe chain for this key ends here.
+                if match_byte_mask(word, EMPTY) != 0 {
+                    return None;
+                }
+
+                for slot in 0..GROUP_SIZE {
+                    let index = group_base + slot;
+                    if self.probe_distance[index] < self.distance_from(base, index) {
+                        return None;
+                    }
+                }
+
+                group = (group + 1) % num_groups;
             }
+
+            None
         }
 
         fn get_index(&self, key: &Key) -> Option<usize> {
-            let mut index = key.hash() % self.cells.len();
-            for _ in 0..self.cells.len() {
-                if !self.cells[index].taken {
+            self.locate(self.hash(key), key)
+        }
+
+        pub fn get(&self, key: &Key) -> Option<&Value> {
+            self.get_index(key).map(|index| &self.values[index])
+        }
+
+        pub fn get_mut(&mut self, key: &Key) -> Option<&mut Value> {
+            self.get_index(key).map(|index| &mut self.values[index])
+        }
+
+        pub fn remove(&mut self, key: &Key) -> Option<Value> {
+            let index = self.get_index(key)?;
+            let removed = std::mem::take(&mut self.values[index]);
+            self.keys[index] = Key::default();
+            self.control[index] = EMPTY;
+            self.probe_distance[index] = 0;
+            self.taken_count -= 1;
+            self.shift_back_from(index);
+            Some(removed)
+        }
+
+        // Robin Hood backward-shift deletion: since a slot's probe distance
+        // is how far it sits past its own ideal slot, the slot right after a
+        // hole can only move back into it if it had to travel past its own
+        // ideal slot to get in, i.e. its probe distance is nonzero. Slide such slots back one
+        // at a time (shortening their distance by one each time) until the
+        // chain hits an empty slot or one that's already sitting at its own
+        // ideal slot. No tombstone needed.
+        fn shift_back_from(&mut self, mut hole: usize) {
+            loop {
+                let next = (hole + 1) % self.capacity();
+                if self.control[next] & 0x80 != 0 || self.probe_distance[next] == 0 {
                     break;
                 }
 
-                if self.cells[index].key == *key {
-                    break;
+                self.control[hole] = self.control[next];
+                self.keys[hole] = self.keys[next].clone();
+                self.values[hole] = std::mem::take(&mut self.values[next]);
+                self.probe_distance[hole] = self.probe_distance[next] - 1;
+
+                self.control[next] = EMPTY;
+                self.keys[next] = Key::default();
+                self.probe_distance[next] = 0;
+
+                hole = next;
+            }
+        }
+
+        // Looks up `key` once and hands back a handle to either its existing
+        // slot, or its already-computed hash ready for a vacant insert. Robin
+        // Hood insertion can displace other keys on its way to a home, so
+        // unlike a plain probe scheme, a vacant entry can't just remember a
+        // single slot index to write into later. It still saves the hash so
+        // `VacantEntry::insert` never has to re-hash the key, and
+        // `insert_with_robin_hood` hands back the key's final slot so it
+        // never has to re-probe for it either.
+        pub fn entry(&mut self, key: Key) -> Entry<'_, Key, Value, S> {
+            if self.is_overloaded() {
+                self.grow();
+            }
+
+            let hash = self.hash(&key);
+            match self.locate(hash, &key) {
+                Some(index) => Entry::Occupied(OccupiedEntry { table: self, index }),
+                None => Entry::Vacant(VacantEntry { table: self, key, hash }),
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.taken_count
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.taken_count == 0
+        }
+
+        pub fn iter(&self) -> Iter<'_, Key, Value> {
+            Iter { control: &self.control, keys: &self.keys, values: &self.values, index: 0 }
+        }
+
+        pub fn iter_mut(&mut self) -> IterMut<'_, Key, Value> {
+            IterMut { control: &self.control, keys: &self.keys, values: &mut self.values, index: 0 }
+        }
+    }
+
+    pub struct Iter<'a, Key, Value> {
+        control: &'a [u8],
+        keys: &'a [Key],
+        values: &'a [Value],
+        index: usize,
+    }
+
+    impl<'a, Key, Value> Iterator for Iter<'a, Key, Value> {
+        type Item = (&'a Key, &'a Value);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while self.index < self.control.len() {
+                let index = self.index;
+                self.index += 1;
+                if self.control[index] & 0x80 == 0 {
+                    return Some((&self.keys[index], &self.values[index]));
                 }
+            }
+            None
+        }
+    }
 
-                index = (index + 1) % self.cells.len();
+    pub struct IterMut<'a, Key, Value> {
+        control: &'a [u8],
+        keys: &'a [Key],
+        values: &'a mut [Value],
+        index: usize,
+    }
+
+    impl<'a, Key, Value> Iterator for IterMut<'a, Key, Value> {
+        type Item = (&'a Key, &'a mut Value);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            while self.index < self.control.len() {
+                let occupied = self.control[self.index] & 0x80 == 0;
+                let key_index = self.index;
+                self.index += 1;
+
+                let values = std::mem::take(&mut self.values);
+                let (value, rest) = values.split_first_mut().unwrap();
+                self.values = rest;
+
+                if occupied {
+                    return Some((&self.keys[key_index], value));
+                }
             }
+            None
+        }
+    }
 
-            if self.cells[index].taken && self.cells[index].key == *key {
-                return Some(index);
-            } else {
-                None
+    pub struct IntoIter<Key, Value> {
+        control: std::vec::IntoIter<u8>,
+        keys: std::vec::IntoIter<Key>,
+        values: std::vec::IntoIter<Value>,
+    }
+
+    impl<Key, Value> Iterator for IntoIter<Key, Value> {
+        type Item = (Key, Value);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            loop {
+                let control = self.control.next()?;
+                let key = self.keys.next().expect("keys and control stay in lockstep");
+                let value = self.values.next().expect("values and control stay in lockstep");
+                if control & 0x80 == 0 {
+                    return Some((key, value));
+                }
             }
         }
+    }
 
-        pub fn get(&self, key: &Key) -> Option<&Value> {
-            self.get_index(key).map(|index| &self.cells[index].value)
+    impl<Key, Value, S> IntoIterator for HashTable<Key, Value, S> {
+        type Item = (Key, Value);
+        type IntoIter = IntoIter<Key, Value>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            IntoIter {
+                control: self.control.into_iter(),
+                keys: self.keys.into_iter(),
+                values: self.values.into_iter(),
+            }
+        }
+    }
+
+    impl<'a, Key, Value, S> IntoIterator for &'a HashTable<Key, Value, S>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+        S: BuildHasher + Clone,
+    {
+        type Item = (&'a Key, &'a Value);
+        type IntoIter = Iter<'a, Key, Value>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter()
         }
+    }
 
-        pub fn get_mut(&mut self, key: &Key) -> Option<&mut Value> {
-            self.get_index(key).map(|index| &mut self.cells[index].value)
+    impl<'a, Key, Value, S> IntoIterator for &'a mut HashTable<Key, Value, S>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+        S: BuildHasher + Clone,
+    {
+        type Item = (&'a Key, &'a mut Value);
+        type IntoIter = IterMut<'a, Key, Value>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.iter_mut()
+        }
+    }
+
+    impl<Key, Value> FromIterator<(Key, Value)> for HashTable<Key, Value, RandomState>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+    {
+        fn from_iter<I: IntoIterator<Item = (Key, Value)>>(iter: I) -> Self {
+            let mut table = Self::new();
+            table.extend(iter);
+            table
+        }
+    }
+
+    impl<Key, Value, S> Extend<(Key, Value)> for HashTable<Key, Value, S>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+        S: BuildHasher + Clone,
+    {
+        fn extend<I: IntoIterator<Item = (Key, Value)>>(&mut self, iter: I) {
+            for (key, value) in iter {
+                self.insert(key, value);
+            }
+        }
+    }
+
+    pub enum Entry<'a, Key, Value, S> {
+        Occupied(OccupiedEntry<'a, Key, Value, S>),
+        Vacant(VacantEntry<'a, Key, Value, S>),
+    }
+
+    impl<'a, Key, Value, S> Entry<'a, Key, Value, S>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+        S: BuildHasher + Clone,
+    {
+        pub fn or_insert(self, default: Value) -> &'a mut Value {
+            self.or_insert_with(|| default)
+        }
+
+        pub fn or_insert_with<F: FnOnce() -> Value>(self, default: F) -> &'a mut Value {
+            match self {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(default()),
+            }
+        }
+
+        pub fn and_modify<F: FnOnce(&mut Value)>(mut self, f: F) -> Self {
+            if let Entry::Occupied(entry) = &mut self {
+                f(entry.get_mut());
+            }
+            self
+        }
+    }
+
+    pub struct OccupiedEntry<'a, Key, Value, S> {
+        table: &'a mut HashTable<Key, Value, S>,
+        index: usize,
+    }
+
+    impl<'a, Key, Value, S> OccupiedEntry<'a, Key, Value, S> {
+        pub fn get(&self) -> &Value {
+            &self.table.values[self.index]
+        }
+
+        pub fn get_mut(&mut self) -> &mut Value {
+            &mut self.table.values[self.index]
+        }
+
+        pub fn into_mut(self) -> &'a mut Value {
+            &mut self.table.values[self.index]
+        }
+    }
+
+    pub struct VacantEntry<'a, Key, Value, S> {
+        table: &'a mut HashTable<Key, Value, S>,
+        key: Key,
+        hash: usize,
+    }
+
+    impl<'a, Key, Value, S> VacantEntry<'a, Key, Value, S>
+    where
+        Key: Default + Clone + Debug + Eq + Hash,
+        Value: Default + Clone + Debug,
+        S: BuildHasher + Clone,
+    {
+        pub fn insert(self, value: Value) -> &'a mut Value {
+            let index = self.table.insert_with_robin_hood(self.hash, self.key, value);
+            self.table.taken_count += 1;
+            &mut self.table.values[index]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::collections::HashMap;
+
+        #[test]
+        fn remove_on_empty_table_returns_none() {
+            let mut table = HashTable::<usize, usize>::new();
+            assert_eq!(table.remove(&0), None);
+        }
+
+        #[test]
+        fn remove_then_reinsert_survives_a_resize() {
+            let mut table = HashTable::<usize, usize>::with_load_factor(0.5);
+            for key in 0..8 {
+                table.insert(key, key * 10);
+            }
+            for key in 0..4 {
+                assert_eq!(table.remove(&key), Some(key * 10));
+            }
+            // Growing past the freed slots must still find every surviving key.
+            for key in 100..200 {
+                table.insert(key, key);
+            }
+            for key in 4..8 {
+                assert_eq!(table.get(&key), Some(&(key * 10)));
+            }
+            for key in 100..200 {
+                assert_eq!(table.get(&key), Some(&key));
+            }
+        }
+
+        #[test]
+        fn matches_std_hashmap_under_random_insert_remove_get() {
+            let mut model: HashMap<usize, usize> = HashMap::new();
+            let mut table = HashTable::<usize, usize>::new();
+
+            for _ in 0..10_000 {
+                let key = rand::random::<usize>() % 500;
+                match rand::random::<usize>() % 3 {
+                    0 => {
+                        let value = rand::random::<usize>();
+                        model.insert(key, value);
+                        table.insert(key, value);
+                    }
+                    1 => {
+                        assert_eq!(model.remove(&key), table.remove(&key));
+                    }
+                    _ => {
+                        assert_eq!(model.get(&key), table.get(&key));
+                    }
+                }
+            }
+
+            for (key, value) in &model {
+                assert_eq!(table.get(key), Some(value));
+            }
+        }
+
+        #[test]
+        fn entry_or_insert_and_modify_match_std_hashmap() {
+            let mut model: HashMap<usize, usize> = HashMap::new();
+            let mut table = HashTable::<usize, usize>::new();
+
+            for _ in 0..10_000 {
+                let key = rand::random::<usize>() % 500;
+                model.entry(key).and_modify(|value| *value += 1).or_insert(1);
+                table.entry(key).and_modify(|value| *value += 1).or_insert(1);
+            }
+
+            for (key, value) in &model {
+                assert_eq!(table.get(key), Some(value));
+            }
+        }
+
+        #[test]
+        fn entry_triggers_a_grow_past_the_load_factor() {
+            let mut table = HashTable::<usize, usize>::with_load_factor(0.5);
+            for key in 0..64 {
+                table.entry(key).or_insert(key * 10);
+            }
+            for key in 0..64 {
+                assert_eq!(table.get(&key), Some(&(key * 10)));
+            }
+        }
+
+        #[test]
+        fn iter_and_iter_mut_visit_exactly_the_live_entries() {
+            let mut table = HashTable::<usize, usize>::new();
+            for key in 0..50 {
+                table.insert(key, key * 10);
+            }
+            for key in 0..20 {
+                table.remove(&key);
+            }
+
+            assert_eq!(table.len(), 30);
+
+            let mut seen: Vec<usize> = table.iter().map(|(key, _)| *key).collect();
+            seen.sort();
+            assert_eq!(seen, (20..50).collect::<Vec<_>>());
+
+            for (_, value) in table.iter_mut() {
+                *value += 1;
+            }
+            for key in 20..50 {
+                assert_eq!(table.get(&key), Some(&(key * 10 + 1)));
+            }
+        }
+
+        #[test]
+        fn into_iterator_from_iterator_and_extend_round_trip_match_std_hashmap() {
+            let model: HashMap<usize, usize> = (0..200).map(|key| (key, key * 10)).collect();
+            let mut table: HashTable<usize, usize> = model.clone().into_iter().collect();
+
+            assert_eq!(table.len(), model.len());
+            for (key, value) in &model {
+                assert_eq!(table.get(key), Some(value));
+            }
+
+            table.extend((200..300).map(|key| (key, key * 10)));
+            for key in 200..300 {
+                assert_eq!(table.get(&key), Some(&(key * 10)));
+            }
+
+            let mut collected: Vec<(usize, usize)> = table.into_iter().collect();
+            collected.sort();
+            let mut expected: Vec<(usize, usize)> = (0..300).map(|key| (key, key * 10)).collect();
+            expected.sort();
+            assert_eq!(collected, expected);
         }
     }
 }
@@ -137,11 +708,7 @@ fn benchmark_our_virgin_table(n: usize) {
     let mut hash = HashTable::<usize, usize>::new();
     for _ in 0..n {
         let key = rand::random::<usize>();
-        if let Some(value) = hash.get_mut(&key) {
-            *value += 1;
-        } else {
-            hash.insert(key, 1);
-        }
+        hash.entry(key).and_modify(|value| *value += 1).or_insert(1);
     }
 }
 